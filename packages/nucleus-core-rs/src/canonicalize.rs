@@ -1,5 +1,10 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
 use serde_json::{Value, Map};
-use std::io::Write;
+
+use crate::CoreError;
 
 /// Canonicalize JSON according to JCS (RFC 8785) style
 /// 
@@ -8,87 +13,142 @@ use std::io::Write;
 /// - No whitespace
 /// - Unicode escape sequences normalized
 /// - Numbers in standard JSON representation
-pub fn canonicalize_json(value: &Value) -> Result<Vec<u8>, String> {
+pub fn canonicalize_json(value: &Value) -> Result<Vec<u8>, CoreError> {
     let mut buffer = Vec::new();
-    write_canonical(&mut buffer, value)
-        .map_err(|e| format!("Failed to write canonical JSON: {}", e))?;
+    canonicalize_json_into(value, &mut buffer)?;
     Ok(buffer)
 }
 
-fn write_canonical<W: Write>(writer: &mut W, value: &Value) -> std::io::Result<()> {
+/// Append the canonical form of `value` to `buffer`
+///
+/// Lets hot paths reuse one allocation across many records; clear the
+/// buffer between calls to canonicalize each record on its own.
+pub fn canonicalize_json_into(value: &Value, buffer: &mut Vec<u8>) -> Result<(), CoreError> {
+    write_canonical(&mut ByteWriter(buffer), value).map_err(write_error)
+}
+
+/// `fmt::Write` adapter appending UTF-8 to a byte buffer
+pub(crate) struct ByteWriter<'a>(pub(crate) &'a mut Vec<u8>);
+
+impl Write for ByteWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+pub(crate) fn write_error(e: core::fmt::Error) -> CoreError {
+    CoreError::Canonicalization(format!("Failed to write canonical JSON: {}", e))
+}
+
+pub(crate) fn write_canonical<W: Write>(writer: &mut W, value: &Value) -> core::fmt::Result {
     match value {
-        Value::Null => write!(writer, "null"),
-        Value::Bool(b) => write!(writer, "{}", b),
+        Value::Null => writer.write_str("null"),
+        Value::Bool(b) => writer.write_str(if *b { "true" } else { "false" }),
         Value::Number(n) => {
             // Use serde_json's number formatting (already canonical)
             write!(writer, "{}", n)
         }
-        Value::String(s) => {
-            // Write JSON-escaped string
-            write!(writer, "\"{}\"", escape_json_string(s))
-        }
+        Value::String(s) => write_canonical_str(writer, s),
         Value::Array(arr) => {
-            write!(writer, "[")?;
+            writer.write_char('[')?;
             for (i, item) in arr.iter().enumerate() {
                 if i > 0 {
-                    write!(writer, ",")?;
+                    writer.write_char(',')?;
                 }
                 write_canonical(writer, item)?;
             }
-            write!(writer, "]")
+            writer.write_char(']')
         }
         Value::Object(obj) => {
-            write_canonical_object(writer, obj)
+            write_canonical_object(writer, obj, &[])
         }
     }
 }
 
-fn write_canonical_object<W: Write>(writer: &mut W, obj: &Map<String, Value>) -> std::io::Result<()> {
-    write!(writer, "{{")?;
-    
+/// Write an object with `leading` keys first (in the given order, when
+/// present) and all remaining keys sorted
+pub(crate) fn write_canonical_object<W: Write>(
+    writer: &mut W,
+    obj: &Map<String, Value>,
+    leading: &[String],
+) -> core::fmt::Result {
     // Sort keys lexicographically
-    let mut keys: Vec<&String> = obj.keys().collect();
+    let mut keys: Vec<&String> = if leading.is_empty() {
+        obj.keys().collect()
+    } else {
+        obj.keys().filter(|k| !leading.contains(k)).collect()
+    };
     keys.sort();
+    if leading.is_empty() {
+        return write_members(writer, obj, keys);
+    }
+
+    let first = leading
+        .iter()
+        .enumerate()
+        .filter(|(i, k)| obj.contains_key(*k) && !leading[..*i].contains(k))
+        .map(|(_, k)| k);
+    write_members(writer, obj, first.chain(keys))
+}
+
+fn write_members<'a, W: Write>(
+    writer: &mut W,
+    obj: &'a Map<String, Value>,
+    keys: impl IntoIterator<Item = &'a String>,
+) -> core::fmt::Result {
+    writer.write_char('{')?;
     
-    for (i, key) in keys.iter().enumerate() {
+    for (i, key) in keys.into_iter().enumerate() {
         if i > 0 {
-            write!(writer, ",")?;
+            writer.write_char(',')?;
         }
         
         // Write key
-        write!(writer, "\"{}\":", escape_json_string(key))?;
+        write_canonical_str(writer, key)?;
+        writer.write_char(':')?;
         
         // Write value
-        if let Some(value) = obj.get(*key) {
+        if let Some(value) = obj.get(key) {
             write_canonical(writer, value)?;
         }
     }
     
-    write!(writer, "}}")
+    writer.write_char('}')
 }
 
-/// Escape string for JSON (handles quotes, backslashes, control chars)
-fn escape_json_string(s: &str) -> String {
-    let mut result = String::new();
+/// Write `s` as a quoted JSON string (handles quotes, backslashes, control chars)
+///
+/// Unescaped runs are written as slices of `s`, so nothing is allocated.
+pub(crate) fn write_canonical_str<W: Write>(writer: &mut W, s: &str) -> core::fmt::Result {
+    writer.write_char('"')?;
+    let mut start = 0;
     
-    for ch in s.chars() {
-        match ch {
-            '"' => result.push_str("\\\""),
-            '\\' => result.push_str("\\\\"),
-            '\u{0008}' => result.push_str("\\b"),
-            '\u{000C}' => result.push_str("\\f"),
-            '\n' => result.push_str("\\n"),
-            '\r' => result.push_str("\\r"),
-            '\t' => result.push_str("\\t"),
+    for (i, ch) in s.char_indices() {
+        let escape = match ch {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\u{0008}' => "\\b",
+            '\u{000C}' => "\\f",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
             c if c.is_control() => {
                 // Unicode escape for control characters
-                result.push_str(&format!("\\u{:04x}", c as u32));
+                writer.write_str(&s[start..i])?;
+                write!(writer, "\\u{:04x}", c as u32)?;
+                start = i + c.len_utf8();
+                continue;
             }
-            c => result.push(c),
-        }
+            _ => continue,
+        };
+        writer.write_str(&s[start..i])?;
+        writer.write_str(escape)?;
+        start = i + ch.len_utf8();
     }
     
-    result
+    writer.write_str(&s[start..])?;
+    writer.write_char('"')
 }
 
 #[cfg(test)]
@@ -111,6 +171,7 @@ mod tests {
     }
     
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_number() {
         let value = json!(42);
         let canonical = canonicalize_json(&value).unwrap();
@@ -215,5 +276,11 @@ mod tests {
         
         assert_eq!(canonical1, canonical2);
     }
+    
+    #[test]
+    fn test_canonicalize_into_appends() {
+        let mut buffer = b"prefix:".to_vec();
+        canonicalize_json_into(&json!({"b": [1, "x"], "a": null}), &mut buffer).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), r#"prefix:{"a":null,"b":[1,"x"]}"#);
+    }
 }
-
@@ -0,0 +1,250 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use serde_json::Value;
+use sha2::{Sha256, Digest};
+
+use crate::canonicalize::{write_canonical, write_canonical_str, write_error, ByteWriter};
+use crate::{encode_hash, hash_bytes, hash_canonical, CoreError};
+
+/// Tag prepended to every domain-separated (v3+) hash input
+///
+/// Canonical JSON always starts with `{`, `[`, `"` or a scalar, so tagged
+/// inputs can never collide with v1/v2 inputs.
+const DOMAIN_TAG_V3: &[u8] = b"nucleus-entry/v3";
+
+/// Entry hashing scheme
+///
+/// Recorded per entry so chains written under different schemes can still
+/// be verified entry by entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HashVersion {
+    /// `H(canonical(record))`: the link to the previous entry is asserted
+    /// by storage but not covered by the hash
+    V1,
+    /// `H(canonical({prev_hash, record, sequence}))`: the hash commits to
+    /// the previous entry and the entry's position
+    V2,
+    /// V2 input prefixed with a version tag and the `HashDomain`, so hashes
+    /// are bound to one ledger and entry type
+    V3,
+}
+
+impl HashVersion {
+    /// Scheme used for newly appended entries
+    pub const CURRENT: HashVersion = HashVersion::V3;
+
+    /// Numeric identifier as stored alongside entries
+    pub fn as_u32(self) -> u32 {
+        match self {
+            HashVersion::V1 => 1,
+            HashVersion::V2 => 2,
+            HashVersion::V3 => 3,
+        }
+    }
+
+    /// Parse a stored identifier
+    pub fn from_u32(version: u32) -> Result<Self, CoreError> {
+        match version {
+            1 => Ok(HashVersion::V1),
+            2 => Ok(HashVersion::V2),
+            3 => Ok(HashVersion::V3),
+            other => Err(CoreError::UnsupportedHashVersion(other)),
+        }
+    }
+}
+
+/// Domain an entry hash is bound to (used from `HashVersion::V3`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashDomain<'a> {
+    /// Ledger the entry belongs to
+    pub ledger_id: &'a str,
+    /// Kind of entry, e.g. `"record"` or `"anchor"`
+    pub entry_type: &'a str,
+}
+
+/// Compute the hash of a chain entry under `version`
+///
+/// `prev_hash` is `None` for the genesis entry. `domain` is ignored by
+/// versions before V3.
+pub fn compute_entry_hash(
+    version: HashVersion,
+    domain: &HashDomain,
+    record: &Value,
+    prev_hash: Option<&str>,
+    sequence: u64,
+) -> Result<String, CoreError> {
+    match version {
+        HashVersion::V1 => hash_canonical(record),
+        HashVersion::V2 => Ok(hash_bytes(&canonical_linked(record, prev_hash, sequence)?)),
+        HashVersion::V3 => {
+            let canonical_bytes = canonical_linked(record, prev_hash, sequence)?;
+
+            let mut hasher = Sha256::new();
+            hasher.update(DOMAIN_TAG_V3);
+            // Length-prefix each field so ("ab", "c") and ("a", "bc") differ;
+            // u64 so no field length can be truncated
+            for field in [domain.ledger_id, domain.entry_type] {
+                hasher.update((field.len() as u64).to_be_bytes());
+                hasher.update(field.as_bytes());
+            }
+            hasher.update(&canonical_bytes);
+
+            Ok(encode_hash(&hasher.finalize()))
+        }
+    }
+}
+
+/// Canonical bytes of `{"prev_hash", "record", "sequence"}`
+///
+/// Written field by field (keys already in sorted order) so the record is
+/// not copied into a temporary `Value`.
+fn canonical_linked(record: &Value, prev_hash: Option<&str>, sequence: u64) -> Result<Vec<u8>, CoreError> {
+    let mut buffer = Vec::new();
+    write_linked(&mut ByteWriter(&mut buffer), record, prev_hash, sequence).map_err(write_error)?;
+    Ok(buffer)
+}
+
+fn write_linked<W: Write>(writer: &mut W, record: &Value, prev_hash: Option<&str>, sequence: u64) -> fmt::Result {
+    writer.write_str("{\"prev_hash\":")?;
+    match prev_hash {
+        Some(hash) => write_canonical_str(writer, hash)?,
+        None => writer.write_str("null")?,
+    }
+    writer.write_str(",\"record\":")?;
+    write_canonical(writer, record)?;
+    write!(writer, ",\"sequence\":{}}}", sequence)
+}
+
+/// Recompute an entry hash under `version` and compare it with `expected`
+pub fn verify_entry_hash(
+    version: HashVersion,
+    domain: &HashDomain,
+    record: &Value,
+    prev_hash: Option<&str>,
+    sequence: u64,
+    expected: &str,
+) -> Result<bool, CoreError> {
+    Ok(compute_entry_hash(version, domain, record, prev_hash, sequence)? == expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonicalize_json;
+    use serde_json::json;
+
+    const DOMAIN: HashDomain = HashDomain {
+        ledger_id: "ledger-a",
+        entry_type: "record",
+    };
+
+    #[test]
+    fn test_v1_matches_record_hash() {
+        let record = json!({"id": "a", "payload": {"x": 1}});
+        assert_eq!(
+            compute_entry_hash(HashVersion::V1, &DOMAIN, &record, Some("prev"), 7).unwrap(),
+            hash_canonical(&record).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_linked_form_matches_jcs() {
+        let record = json!({"id": "a", "text": "quote \" and \u{1}"});
+        for prev_hash in [None, Some("prev\\hash")] {
+            let expected = json!({"prev_hash": prev_hash, "record": record, "sequence": u64::MAX});
+            assert_eq!(
+                canonical_linked(&record, prev_hash, u64::MAX).unwrap(),
+                canonicalize_json(&expected).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_v2_commits_to_prev_hash() {
+        let record = json!({"id": "a"});
+        let genesis = compute_entry_hash(HashVersion::V2, &DOMAIN, &record, None, 0).unwrap();
+        let linked = compute_entry_hash(HashVersion::V2, &DOMAIN, &record, Some(&genesis), 0).unwrap();
+        let relinked = compute_entry_hash(HashVersion::V2, &DOMAIN, &record, Some("other"), 0).unwrap();
+
+        assert_ne!(genesis, linked);
+        assert_ne!(linked, relinked);
+    }
+
+    #[test]
+    fn test_v2_commits_to_sequence() {
+        let record = json!({"id": "a"});
+        let first = compute_entry_hash(HashVersion::V2, &DOMAIN, &record, Some("prev"), 1).unwrap();
+        let moved = compute_entry_hash(HashVersion::V2, &DOMAIN, &record, Some("prev"), 2).unwrap();
+        assert_ne!(first, moved);
+    }
+
+    #[test]
+    fn test_verify_entry_hash() {
+        let record = json!({"id": "a"});
+        let hash = compute_entry_hash(HashVersion::V2, &DOMAIN, &record, Some("prev"), 3).unwrap();
+
+        assert!(verify_entry_hash(HashVersion::V2, &DOMAIN, &record, Some("prev"), 3, &hash).unwrap());
+        // Reordering (different sequence) is detected by recomputation alone
+        assert!(!verify_entry_hash(HashVersion::V2, &DOMAIN, &record, Some("prev"), 4, &hash).unwrap());
+        // V1 does not commit to the link, so the same check cannot detect it
+        let v1 = compute_entry_hash(HashVersion::V1, &DOMAIN, &record, None, 0).unwrap();
+        assert!(verify_entry_hash(HashVersion::V1, &DOMAIN, &record, Some("other"), 9, &v1).unwrap());
+    }
+
+    #[test]
+    fn test_v3_input_layout() {
+        let record = json!({"id": "a"});
+        let mut input = Vec::from(DOMAIN_TAG_V3);
+        for field in ["ledger-a", "record"] {
+            input.extend_from_slice(&(field.len() as u64).to_be_bytes());
+            input.extend_from_slice(field.as_bytes());
+        }
+        input.extend_from_slice(&canonical_linked(&record, None, 0).unwrap());
+
+        assert_eq!(
+            compute_entry_hash(HashVersion::V3, &DOMAIN, &record, None, 0).unwrap(),
+            hash_bytes(&input)
+        );
+    }
+
+    #[test]
+    fn test_v3_binds_domain() {
+        let record = json!({"id": "a"});
+        let hash = compute_entry_hash(HashVersion::V3, &DOMAIN, &record, Some("prev"), 1).unwrap();
+
+        let other_ledger = HashDomain { ledger_id: "ledger-b", ..DOMAIN };
+        let other_type = HashDomain { entry_type: "anchor", ..DOMAIN };
+        for domain in [other_ledger, other_type] {
+            assert_ne!(
+                compute_entry_hash(HashVersion::V3, &domain, &record, Some("prev"), 1).unwrap(),
+                hash
+            );
+        }
+
+        // Same linked content under V2 hashes differently
+        assert_ne!(
+            compute_entry_hash(HashVersion::V2, &DOMAIN, &record, Some("prev"), 1).unwrap(),
+            hash
+        );
+    }
+
+    #[test]
+    fn test_v3_domain_fields_are_unambiguous() {
+        let record = json!({"id": "a"});
+        let left = HashDomain { ledger_id: "ab", entry_type: "c" };
+        let right = HashDomain { ledger_id: "a", entry_type: "bc" };
+        assert_ne!(
+            compute_entry_hash(HashVersion::V3, &left, &record, None, 0).unwrap(),
+            compute_entry_hash(HashVersion::V3, &right, &record, None, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_version_roundtrip() {
+        for version in [HashVersion::V1, HashVersion::V2, HashVersion::V3] {
+            assert_eq!(HashVersion::from_u32(version.as_u32()).unwrap(), version);
+        }
+        assert!(HashVersion::from_u32(99).is_err());
+    }
+}
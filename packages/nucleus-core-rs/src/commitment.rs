@@ -0,0 +1,127 @@
+use alloc::format;
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{hash_bytes, CoreError};
+
+/// Hash algorithm identifier written into commitments
+pub const COMMITMENT_HASH_ALG: &str = "sha256";
+
+/// Payload of a hash-only record committing to off-ledger data
+///
+/// Only the digest (and optionally size, location and media type) goes on
+/// the ledger; the data itself stays wherever `location` points.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExternalCommitment {
+    /// SHA-256 of the data (base64url)
+    pub data_hash: String,
+    /// Always `"sha256"` for commitments produced by this crate
+    pub hash_alg: String,
+    /// Data length in bytes
+    pub size: u64,
+    /// Where the data can be fetched, e.g. a URL or object key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// MIME type of the data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+}
+
+impl ExternalCommitment {
+    /// Commit to `data`
+    pub fn new(data: &[u8]) -> Self {
+        Self {
+            data_hash: hash_bytes(data),
+            hash_alg: String::from(COMMITMENT_HASH_ALG),
+            size: data.len() as u64,
+            location: None,
+            media_type: None,
+        }
+    }
+
+    /// Set where the data is stored
+    pub fn with_location(mut self, location: impl Into<String>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// Set the data's MIME type
+    pub fn with_media_type(mut self, media_type: impl Into<String>) -> Self {
+        self.media_type = Some(media_type.into());
+        self
+    }
+
+    /// Record payload for this commitment
+    pub fn to_payload(&self) -> Value {
+        // Plain strings and integers always serialize
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
+    /// Read a commitment back from a record payload
+    pub fn from_payload(payload: &Value) -> Result<Self, CoreError> {
+        let commitment: Self = serde_json::from_value(payload.clone())
+            .map_err(|e| CoreError::InvalidPayload(format!("not an external commitment: {}", e)))?;
+        if commitment.hash_alg != COMMITMENT_HASH_ALG {
+            return Err(CoreError::InvalidPayload(format!(
+                "unsupported hash_alg: {}",
+                commitment.hash_alg
+            )));
+        }
+        Ok(commitment)
+    }
+
+    /// Whether `data` is exactly the committed data
+    pub fn verify(&self, data: &[u8]) -> bool {
+        self.size == data.len() as u64 && self.data_hash == hash_bytes(data)
+    }
+}
+
+/// Recompute the digest of `data` and compare it with a commitment payload
+pub fn verify_external_data(payload: &Value, data: &[u8]) -> Result<bool, CoreError> {
+    Ok(ExternalCommitment::from_payload(payload)?.verify(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_commit_and_verify() {
+        let data = b"%PDF-1.7 contract";
+        let commitment = ExternalCommitment::new(data)
+            .with_location("s3://contracts/2024/17.pdf")
+            .with_media_type("application/pdf");
+        let payload = commitment.to_payload();
+
+        assert_eq!(payload["hash_alg"], "sha256");
+        assert_eq!(payload["size"], data.len());
+        assert!(verify_external_data(&payload, data).unwrap());
+        assert!(!verify_external_data(&payload, b"%PDF-1.7 contracT").unwrap());
+    }
+
+    #[test]
+    fn test_optional_fields_omitted() {
+        let payload = ExternalCommitment::new(b"x").to_payload();
+        assert!(payload.get("location").is_none());
+        assert!(payload.get("media_type").is_none());
+    }
+
+    #[test]
+    fn test_payload_roundtrip() {
+        let commitment = ExternalCommitment::new(b"data").with_location("ipfs://cid");
+        assert_eq!(ExternalCommitment::from_payload(&commitment.to_payload()).unwrap(), commitment);
+    }
+
+    #[test]
+    fn test_rejects_invalid_payloads() {
+        let err = verify_external_data(&json!({"data_hash": "x"}), b"").unwrap_err();
+        assert_eq!(err.code(), "INVALID_PAYLOAD");
+
+        let mut payload = ExternalCommitment::new(b"data").to_payload();
+        payload["hash_alg"] = json!("md5");
+        let err = verify_external_data(&payload, b"data").unwrap_err();
+        assert_eq!(err.code(), "INVALID_PAYLOAD");
+    }
+}
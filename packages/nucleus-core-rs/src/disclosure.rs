@@ -0,0 +1,244 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{decode_hash, hash_canonical, CoreError};
+
+/// Salted per-field commitments over a payload object
+///
+/// A record stores only `root()` instead of the payload. The holder keeps the
+/// payload, salts and commitments and can later disclose any subset of
+/// top-level fields. Verifiers get `digests()` rather than this map, so they
+/// learn neither the values nor the names of undisclosed fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldCommitments {
+    /// Field name -> salted field hash (base64url)
+    pub fields: BTreeMap<String, String>,
+}
+
+/// One disclosed field with the salt that opens its commitment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Disclosure {
+    /// Top-level field name
+    pub field: String,
+    /// Field value as committed
+    pub value: Value,
+    /// Salt used when committing the field
+    pub salt: String,
+}
+
+impl Disclosure {
+    /// Salted field hash: `hash_canonical([salt, field, value])`
+    pub fn hash(&self) -> Result<String, CoreError> {
+        field_hash(&self.salt, &self.field, &self.value)
+    }
+}
+
+impl FieldCommitments {
+    /// Commit to every top-level field of `payload`
+    ///
+    /// Salts are supplied by the caller (the core has no randomness source) and
+    /// should carry at least 128 bits of entropy, one per field.
+    pub fn commit(payload: &Value, salts: &BTreeMap<String, String>) -> Result<Self, CoreError> {
+        let fields = payload_object(payload)?
+            .iter()
+            .map(|(field, value)| Ok((field.clone(), field_hash(salt_for(salts, field)?, field, value)?)))
+            .collect::<Result<_, CoreError>>()?;
+        Ok(Self { fields })
+    }
+
+    /// Sorted field hashes without their field names
+    ///
+    /// This is what a verifier receives with the disclosures. Each hash
+    /// already covers its field name, so the names need not be shared.
+    pub fn digests(&self) -> Vec<String> {
+        let mut digests: Vec<String> = self.fields.values().cloned().collect();
+        digests.sort();
+        digests
+    }
+
+    /// Hash committing to the full set of field hashes
+    ///
+    /// Covers the sorted `digests()` only. Verifiers must check the digests
+    /// they receive against the recorded root; otherwise a holder could swap
+    /// in field hashes for values they chose.
+    pub fn root(&self) -> Result<String, CoreError> {
+        digest_root(&self.digests())
+    }
+
+    /// Whether every disclosure opens the commitment for its field
+    pub fn verify(&self, disclosures: &[Disclosure]) -> Result<bool, CoreError> {
+        for disclosure in disclosures {
+            match self.fields.get(&disclosure.field) {
+                Some(expected) if *expected == disclosure.hash()? => {}
+                _ => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Disclosures for the named fields of `payload`
+pub fn disclose(
+    payload: &Value,
+    salts: &BTreeMap<String, String>,
+    fields: &[&str],
+) -> Result<Vec<Disclosure>, CoreError> {
+    let obj = payload_object(payload)?;
+    fields
+        .iter()
+        .map(|field| {
+            let value = obj
+                .get(*field)
+                .ok_or_else(|| CoreError::InvalidPayload(format!("no field {:?} to disclose", field)))?;
+            Ok(Disclosure {
+                field: String::from(*field),
+                value: value.clone(),
+                salt: String::from(salt_for(salts, field)?),
+            })
+        })
+        .collect()
+}
+
+/// Check disclosed fields against the commitment root stored in a record
+///
+/// `digests` is the holder's `FieldCommitments::digests()`, in any order. It
+/// reveals how many fields were committed, but not their names or values.
+pub fn verify_disclosures(root: &str, digests: &[String], disclosures: &[Disclosure]) -> Result<bool, CoreError> {
+    decode_hash(root)?;
+    let mut sorted = digests.to_vec();
+    sorted.sort();
+    if digest_root(&sorted)? != root {
+        return Ok(false);
+    }
+    for disclosure in disclosures {
+        if sorted.binary_search(&disclosure.hash()?).is_err() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn digest_root(sorted: &[String]) -> Result<String, CoreError> {
+    hash_canonical(&Value::Array(sorted.iter().cloned().map(Value::String).collect()))
+}
+
+fn field_hash(salt: &str, field: &str, value: &Value) -> Result<String, CoreError> {
+    hash_canonical(&Value::Array(alloc::vec![
+        Value::String(String::from(salt)),
+        Value::String(String::from(field)),
+        value.clone(),
+    ]))
+}
+
+fn payload_object(payload: &Value) -> Result<&Map<String, Value>, CoreError> {
+    payload
+        .as_object()
+        .ok_or_else(|| CoreError::InvalidPayload(String::from("selective disclosure needs an object payload")))
+}
+
+fn salt_for<'a>(salts: &'a BTreeMap<String, String>, field: &str) -> Result<&'a str, CoreError> {
+    match salts.get(field) {
+        Some(salt) if !salt.is_empty() => Ok(salt),
+        _ => Err(CoreError::InvalidPayload(format!("missing salt for field {:?}", field))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn payload() -> Value {
+        json!({"name": "Alice", "birth_date": "1990-01-01", "nationality": "NL"})
+    }
+
+    fn salts() -> BTreeMap<String, String> {
+        [("name", "c2FsdC1uYW1l"), ("birth_date", "c2FsdC1iaXJ0aA"), ("nationality", "c2FsdC1uYXQ")]
+            .iter()
+            .map(|(f, s)| (String::from(*f), String::from(*s)))
+            .collect()
+    }
+
+    #[test]
+    fn test_disclose_subset() {
+        let commitments = FieldCommitments::commit(&payload(), &salts()).unwrap();
+        let root = commitments.root().unwrap();
+
+        let disclosures = disclose(&payload(), &salts(), &["nationality"]).unwrap();
+        assert_eq!(disclosures.len(), 1);
+        assert!(verify_disclosures(&root, &commitments.digests(), &disclosures).unwrap());
+
+        // Digest order does not matter to the verifier
+        let mut reversed = commitments.digests();
+        reversed.reverse();
+        assert!(verify_disclosures(&root, &reversed, &disclosures).unwrap());
+    }
+
+    #[test]
+    fn test_commitments_hide_values() {
+        let commitments = FieldCommitments::commit(&payload(), &salts()).unwrap();
+        let serialized = serde_json::to_string(&commitments).unwrap();
+        assert!(!serialized.contains("Alice"));
+        assert!(!serialized.contains("1990"));
+    }
+
+    #[test]
+    fn test_digests_hide_field_names() {
+        let commitments = FieldCommitments::commit(&payload(), &salts()).unwrap();
+        let serialized = serde_json::to_string(&commitments.digests()).unwrap();
+        for field in ["name", "birth_date", "nationality"] {
+            assert!(!serialized.contains(field));
+        }
+    }
+
+    #[test]
+    fn test_rejects_altered_disclosures() {
+        let commitments = FieldCommitments::commit(&payload(), &salts()).unwrap();
+        let root = commitments.root().unwrap();
+        let digests = commitments.digests();
+        let mut disclosures = disclose(&payload(), &salts(), &["name", "birth_date"]).unwrap();
+
+        disclosures[1].value = json!("2000-01-01");
+        assert!(!verify_disclosures(&root, &digests, &disclosures).unwrap());
+
+        // The field name is part of each hash, so a value cannot be relabelled
+        disclosures[1] = disclose(&payload(), &salts(), &["birth_date"]).unwrap().remove(0);
+        disclosures[1].field = String::from("nationality");
+        assert!(!verify_disclosures(&root, &digests, &disclosures).unwrap());
+    }
+
+    #[test]
+    fn test_rejects_swapped_commitments() {
+        let commitments = FieldCommitments::commit(&payload(), &salts()).unwrap();
+        let root = commitments.root().unwrap();
+
+        // A verifier only trusts digests that hash to the recorded root
+        let mut forged = commitments.clone();
+        let disclosures = [Disclosure { field: "name".into(), value: json!("Mallory"), salt: "x".into() }];
+        forged.fields.insert("name".into(), disclosures[0].hash().unwrap());
+        assert!(!verify_disclosures(&root, &forged.digests(), &disclosures).unwrap());
+
+        // Appending a digest changes the root as well
+        let mut padded = commitments.digests();
+        padded.push(disclosures[0].hash().unwrap());
+        assert!(!verify_disclosures(&root, &padded, &disclosures).unwrap());
+    }
+
+    #[test]
+    fn test_missing_salt_or_field() {
+        let mut partial = salts();
+        partial.remove("nationality");
+        let err = FieldCommitments::commit(&payload(), &partial).unwrap_err();
+        assert_eq!(err.code(), "INVALID_PAYLOAD");
+
+        let err = disclose(&payload(), &salts(), &["email"]).unwrap_err();
+        assert_eq!(err.code(), "INVALID_PAYLOAD");
+
+        let err = FieldCommitments::commit(&json!(["not", "an", "object"]), &salts()).unwrap_err();
+        assert_eq!(err.code(), "INVALID_PAYLOAD");
+    }
+}
@@ -0,0 +1,136 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Errors returned by nucleus-core
+///
+/// Every variant maps to a stable machine-readable code (`code()`), which is
+/// also attached to errors thrown across the WASM boundary. Codes never
+/// change once released; messages may. New variants can be added in minor
+/// releases, so matches outside this crate need a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CoreError {
+    /// Input could not be read as JSON
+    InvalidJson(String),
+    /// Canonical serialization failed
+    Canonicalization(String),
+    /// A hash string is not a base64url-encoded SHA-256 digest
+    InvalidHash(String),
+    /// A stored hash version is unknown to this build
+    UnsupportedHashVersion(u32),
+    /// An index or size is outside the structure it refers to
+    OutOfRange {
+        /// What was out of range, e.g. `"leaf index"`
+        what: &'static str,
+        /// Requested value
+        index: u64,
+        /// Bound it exceeded
+        size: u64,
+    },
+    /// A persisted MMR node count is not a possible MMR size
+    InvalidMmrSize(u64),
+    /// A persisted MMR parent node does not hash its two children
+    MmrNodeMismatch(u64),
+    /// A record payload does not have the structure an operation expects
+    InvalidPayload(String),
+    /// A result could not be converted for the caller, e.g. into a JS value
+    Serialization(String),
+}
+
+impl CoreError {
+    /// Stable machine-readable error code
+    pub fn code(&self) -> &'static str {
+        match self {
+            CoreError::InvalidJson(_) => "INVALID_JSON",
+            CoreError::Canonicalization(_) => "CANONICALIZATION_FAILED",
+            CoreError::InvalidHash(_) => "INVALID_HASH",
+            CoreError::UnsupportedHashVersion(_) => "UNSUPPORTED_HASH_VERSION",
+            CoreError::OutOfRange { .. } => "OUT_OF_RANGE",
+            CoreError::InvalidMmrSize(_) => "INVALID_MMR_SIZE",
+            CoreError::MmrNodeMismatch(_) => "MMR_NODE_MISMATCH",
+            CoreError::InvalidPayload(_) => "INVALID_PAYLOAD",
+            CoreError::Serialization(_) => "SERIALIZATION_FAILED",
+        }
+    }
+
+    /// Whether the failure may clear up on its own (contention, I/O hiccups)
+    ///
+    /// Core operations are pure functions of their input, so no core error is
+    /// transient. Listed per variant so new variants must be classified.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            CoreError::InvalidJson(_)
+            | CoreError::Canonicalization(_)
+            | CoreError::InvalidHash(_)
+            | CoreError::UnsupportedHashVersion(_)
+            | CoreError::OutOfRange { .. }
+            | CoreError::InvalidMmrSize(_)
+            | CoreError::MmrNodeMismatch(_)
+            | CoreError::InvalidPayload(_)
+            | CoreError::Serialization(_) => false,
+        }
+    }
+
+    /// Whether repeating the same call unchanged can succeed
+    pub fn is_retryable(&self) -> bool {
+        self.is_transient()
+    }
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoreError::InvalidJson(e) => write!(f, "Failed to parse JSON: {}", e),
+            CoreError::Canonicalization(e) => write!(f, "Canonicalization failed: {}", e),
+            CoreError::InvalidHash(e) => write!(f, "Invalid hash: {}", e),
+            CoreError::UnsupportedHashVersion(v) => write!(f, "Unsupported hash version: {}", v),
+            CoreError::OutOfRange { what, index, size } => {
+                write!(f, "{} {} out of range (size {})", what, index, size)
+            }
+            CoreError::InvalidMmrSize(size) => write!(f, "Invalid MMR size: {}", size),
+            CoreError::MmrNodeMismatch(pos) => write!(f, "MMR node {} does not match its children", pos),
+            CoreError::InvalidPayload(e) => write!(f, "Invalid payload: {}", e),
+            CoreError::Serialization(e) => write!(f, "Failed to serialize result: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CoreError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_codes_are_stable() {
+        let cases = [
+            (CoreError::InvalidJson("x".into()), "INVALID_JSON"),
+            (CoreError::Canonicalization("x".into()), "CANONICALIZATION_FAILED"),
+            (CoreError::InvalidHash("x".into()), "INVALID_HASH"),
+            (CoreError::UnsupportedHashVersion(9), "UNSUPPORTED_HASH_VERSION"),
+            (CoreError::OutOfRange { what: "leaf index", index: 3, size: 2 }, "OUT_OF_RANGE"),
+            (CoreError::InvalidMmrSize(2), "INVALID_MMR_SIZE"),
+            (CoreError::MmrNodeMismatch(2), "MMR_NODE_MISMATCH"),
+            (CoreError::InvalidPayload("x".into()), "INVALID_PAYLOAD"),
+            (CoreError::Serialization("x".into()), "SERIALIZATION_FAILED"),
+        ];
+        for (error, code) in cases {
+            assert_eq!(error.code(), code);
+        }
+    }
+
+    #[test]
+    fn test_core_errors_are_not_retryable() {
+        let error = CoreError::InvalidHash("x".into());
+        assert!(!error.is_transient());
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_display() {
+        let error = CoreError::OutOfRange { what: "leaf index", index: 3, size: 2 };
+        assert_eq!(error.to_string(), "leaf index 3 out of range (size 2)");
+    }
+}
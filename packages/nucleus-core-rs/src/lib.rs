@@ -1,44 +1,82 @@
-use wasm_bindgen::prelude::*;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
 use serde_json::Value;
 use sha2::{Sha256, Digest};
 use base64::Engine;
+use alloc::format;
 
 mod canonicalize;
-use canonicalize::canonicalize_json;
+mod chain;
+mod commitment;
+mod disclosure;
+mod error;
+mod merkle;
+mod mmr;
+mod normalize;
+mod path;
+mod profile;
+#[cfg(feature = "testkit")]
+pub mod testkit;
+pub use canonicalize::{canonicalize_json, canonicalize_json_into};
+pub use chain::{compute_entry_hash, verify_entry_hash, HashDomain, HashVersion};
+pub use commitment::{verify_external_data, ExternalCommitment, COMMITMENT_HASH_ALG};
+pub use disclosure::{disclose, verify_disclosures, Disclosure, FieldCommitments};
+pub use error::CoreError;
+pub use merkle::{
+    consistency_proof, inclusion_proof, merkle_root, tree_head_signing_input, verify_consistency,
+    verify_inclusion, ConsistencyProof, InclusionProof, SignedTreeHead,
+};
+pub use mmr::{verify_mmr_proof, MerkleMountainRange, MmrProof};
+pub use normalize::NormalizationPolicy;
+pub use profile::{CanonicalProfile, ProfileRegistry};
+
+/// Raw SHA-256 digest
+pub type HashBytes = [u8; 32];
 
-/// Compute SHA-256 hash of a canonical JSON representation
+/// Compute SHA-256 hash of the canonical JSON representation of `value`
 /// Returns base64url-encoded hash string
-#[wasm_bindgen]
-pub fn compute_hash(record_without_hash: JsValue) -> Result<String, JsValue> {
-    // Deserialize from JS
-    let value: Value = serde_wasm_bindgen::from_value(record_without_hash)
-        .map_err(|e| JsValue::from_str(&format!("Failed to parse JSON: {}", e)))?;
-    
+pub fn hash_canonical(value: &Value) -> Result<String, CoreError> {
     // Canonicalize
-    let canonical_bytes = canonicalize_json(&value)
-        .map_err(|e| JsValue::from_str(&format!("Canonicalization failed: {}", e)))?;
-    
-    // Hash with SHA-256
-    let mut hasher = Sha256::new();
-    hasher.update(&canonical_bytes);
-    let hash_bytes = hasher.finalize();
-    
-    // Encode as base64url (RFC 4648 §5)
-    let base64url_hash = base64::engine::general_purpose::URL_SAFE_NO_PAD
-        .encode(hash_bytes);
+    let canonical_bytes = canonicalize_json(value)?;
     
-    Ok(base64url_hash)
+    Ok(hash_bytes(&canonical_bytes))
 }
 
-/// Canonicalize JSON (for testing/debugging)
-/// Returns canonical JSON bytes
-#[wasm_bindgen]
-pub fn canonicalize(record_without_hash: JsValue) -> Result<Vec<u8>, JsValue> {
-    let value: Value = serde_wasm_bindgen::from_value(record_without_hash)
-        .map_err(|e| JsValue::from_str(&format!("Failed to parse JSON: {}", e)))?;
-    
-    canonicalize_json(&value)
-        .map_err(|e| JsValue::from_str(&format!("Canonicalization failed: {}", e)))
+/// `hash_canonical` over many values, reusing one canonicalization buffer
+pub fn hash_canonical_batch(values: &[Value]) -> Result<Vec<String>, CoreError> {
+    let mut buffer = Vec::new();
+    values
+        .iter()
+        .map(|value| {
+            buffer.clear();
+            canonicalize_json_into(value, &mut buffer)?;
+            Ok(hash_bytes(&buffer))
+        })
+        .collect()
+}
+
+/// SHA-256 of `bytes`, base64url-encoded
+pub(crate) fn hash_bytes(bytes: &[u8]) -> String {
+    encode_hash(&Sha256::digest(bytes))
+}
+
+/// Encode a digest as base64url (RFC 4648 §5), the format used for all hashes
+pub(crate) fn encode_hash(hash_bytes: &[u8]) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(hash_bytes)
+}
+
+/// Decode a base64url hash string into a raw SHA-256 digest
+pub(crate) fn decode_hash(hash: &str) -> Result<HashBytes, CoreError> {
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(hash)
+        .map_err(|e| CoreError::InvalidHash(format!("not base64url: {}", e)))?;
+    bytes.try_into().map_err(|b: alloc::vec::Vec<u8>| {
+        CoreError::InvalidHash(format!("expected 32 bytes, got {}", b.len()))
+    })
 }
 
 #[cfg(test)]
@@ -83,26 +121,31 @@ mod tests {
         let value1 = json!({"b": 2, "a": 1});
         let value2 = json!({"a": 1, "b": 2});
         
-        let hash1 = canonicalize_json(&value1)
-            .and_then(|bytes| {
-                let mut hasher = Sha256::new();
-                hasher.update(&bytes);
-                Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD
-                    .encode(hasher.finalize()))
-            })
-            .unwrap();
-        
-        let hash2 = canonicalize_json(&value2)
-            .and_then(|bytes| {
-                let mut hasher = Sha256::new();
-                hasher.update(&bytes);
-                Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD
-                    .encode(hasher.finalize()))
-            })
-            .unwrap();
+        let hash1 = hash_canonical(&value1).unwrap();
+        let hash2 = hash_canonical(&value2).unwrap();
         
         // Same content (different order) should produce same hash
         assert_eq!(hash1, hash2);
     }
+    
+    #[test]
+    fn test_hash_canonical_batch() {
+        let values = [json!({"b": 2, "a": 1}), json!("x"), json!({"nested": {"z": [1, 2]}})];
+        let hashes = hash_canonical_batch(&values).unwrap();
+        
+        // A longer value earlier in the batch must not leak into later hashes
+        let expected: Vec<String> = values.iter().map(|v| hash_canonical(v).unwrap()).collect();
+        assert_eq!(hashes, expected);
+        assert!(hash_canonical_batch(&[]).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_decode_hash_errors() {
+        let hash = hash_canonical(&json!({"a": 1})).unwrap();
+        assert_eq!(encode_hash(&decode_hash(&hash).unwrap()), hash);
+        
+        // Not base64url, and wrong digest length
+        assert_eq!(decode_hash("not+base64").unwrap_err().code(), "INVALID_HASH");
+        assert_eq!(decode_hash("AAAA").unwrap_err().code(), "INVALID_HASH");
+    }
 }
-
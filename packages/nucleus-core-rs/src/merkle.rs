@@ -0,0 +1,440 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Digest};
+
+use crate::{decode_hash, encode_hash, CoreError, HashBytes};
+
+/// RFC 6962 Merkle tree hash over entry hashes (base64url)
+///
+/// Leaf data is the raw 32-byte entry hash, so a tree over the chain is
+/// `MTH(entry_hash_0, ..., entry_hash_n-1)` as defined in RFC 6962 §2.1.
+pub fn merkle_root<S: AsRef<str>>(entry_hashes: &[S]) -> Result<String, CoreError> {
+    let leaves = decode_leaves(entry_hashes)?;
+    Ok(encode_hash(&tree_hash(&leaves)))
+}
+
+/// Audit path for the leaf at `leaf_index` (RFC 6962 §2.1.1)
+pub fn inclusion_proof<S: AsRef<str>>(entry_hashes: &[S], leaf_index: u64) -> Result<Vec<String>, CoreError> {
+    let leaves = decode_leaves(entry_hashes)?;
+    if leaf_index >= leaves.len() as u64 {
+        return Err(CoreError::OutOfRange {
+            what: "leaf index",
+            index: leaf_index,
+            size: leaves.len() as u64,
+        });
+    }
+    Ok(audit_path(leaf_index as usize, &leaves)
+        .iter()
+        .map(|h| encode_hash(h))
+        .collect())
+}
+
+/// Consistency proof between the first `old_size` entries and all entries (RFC 6962 §2.1.2)
+pub fn consistency_proof<S: AsRef<str>>(entry_hashes: &[S], old_size: u64) -> Result<Vec<String>, CoreError> {
+    let leaves = decode_leaves(entry_hashes)?;
+    if old_size == 0 || old_size > leaves.len() as u64 {
+        return Err(CoreError::OutOfRange {
+            what: "old tree size",
+            index: old_size,
+            size: leaves.len() as u64,
+        });
+    }
+    Ok(subproof(old_size as usize, &leaves, true)
+        .iter()
+        .map(|h| encode_hash(h))
+        .collect())
+}
+
+/// Verify an audit path for `entry_hash` at `leaf_index` (RFC 9162 §2.1.3.2)
+pub fn verify_inclusion(
+    root: &str,
+    entry_hash: &str,
+    leaf_index: u64,
+    tree_size: u64,
+    proof: &[String],
+) -> Result<bool, CoreError> {
+    let root = decode_hash(root)?;
+    let path = decode_leaves(proof)?;
+    if leaf_index >= tree_size {
+        return Ok(false);
+    }
+
+    let mut f_n = leaf_index;
+    let mut s_n = tree_size - 1;
+    let mut r = hash_leaf(&decode_hash(entry_hash)?);
+    for p in &path {
+        if s_n == 0 {
+            return Ok(false);
+        }
+        if f_n & 1 == 1 || f_n == s_n {
+            r = hash_node(p, &r);
+            while f_n & 1 == 0 && f_n != 0 {
+                f_n >>= 1;
+                s_n >>= 1;
+            }
+        } else {
+            r = hash_node(&r, p);
+        }
+        f_n >>= 1;
+        s_n >>= 1;
+    }
+
+    Ok(s_n == 0 && r == root)
+}
+
+/// Verify that the tree of `old_size` entries is a prefix of the tree of `new_size` entries (RFC 9162 §2.1.4.2)
+pub fn verify_consistency(
+    old_size: u64,
+    new_size: u64,
+    old_root: &str,
+    new_root: &str,
+    proof: &[String],
+) -> Result<bool, CoreError> {
+    let old_root = decode_hash(old_root)?;
+    let new_root = decode_hash(new_root)?;
+    let mut path = decode_leaves(proof)?;
+
+    if old_size == new_size {
+        return Ok(path.is_empty() && old_root == new_root);
+    }
+    if old_size == 0 || old_size > new_size || path.is_empty() {
+        return Ok(false);
+    }
+
+    // A complete left subtree is omitted from the proof; its root is the old root
+    if old_size.is_power_of_two() {
+        path.insert(0, old_root);
+    }
+
+    let mut f_n = old_size - 1;
+    let mut s_n = new_size - 1;
+    while f_n & 1 == 1 {
+        f_n >>= 1;
+        s_n >>= 1;
+    }
+
+    let mut f_r = path[0];
+    let mut s_r = path[0];
+    for c in &path[1..] {
+        if s_n == 0 {
+            return Ok(false);
+        }
+        if f_n & 1 == 1 || f_n == s_n {
+            f_r = hash_node(c, &f_r);
+            s_r = hash_node(c, &s_r);
+            while f_n & 1 == 0 && f_n != 0 {
+                f_n >>= 1;
+                s_n >>= 1;
+            }
+        } else {
+            s_r = hash_node(&s_r, c);
+        }
+        f_n >>= 1;
+        s_n >>= 1;
+    }
+
+    Ok(f_r == old_root && s_r == new_root && s_n == 0)
+}
+
+/// Bytes a log signs for a tree head (RFC 6962 §3.5 `TreeHeadSignature`)
+///
+/// The core holds no keys: hosts sign these bytes with their log key to
+/// produce a signed tree head for CT-style monitors.
+pub fn tree_head_signing_input(timestamp_ms: u64, tree_size: u64, root: &str) -> Result<Vec<u8>, CoreError> {
+    let root = decode_hash(root)?;
+    let mut input = Vec::with_capacity(2 + 8 + 8 + 32);
+    input.push(0); // Version: v1
+    input.push(1); // SignatureType: tree_hash
+    input.extend_from_slice(&timestamp_ms.to_be_bytes());
+    input.extend_from_slice(&tree_size.to_be_bytes());
+    input.extend_from_slice(&root);
+    Ok(input)
+}
+
+/// Tree head as published to monitors, with a signature supplied by the host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTreeHead {
+    /// Number of entries covered by `root_hash`
+    pub tree_size: u64,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// RFC 6962 root over the first `tree_size` entry hashes (base64url)
+    pub root_hash: String,
+    /// Host signature over `signing_input()`, in the host's encoding
+    pub signature: String,
+}
+
+impl SignedTreeHead {
+    /// Bytes the signature covers, for checking it against the log key
+    pub fn signing_input(&self) -> Result<Vec<u8>, CoreError> {
+        tree_head_signing_input(self.timestamp, self.tree_size, &self.root_hash)
+    }
+}
+
+/// Serializable inclusion proof for one entry (RFC 9162 §4.11 `InclusionProofDataV2`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InclusionProof {
+    /// Zero-based entry index
+    pub leaf_index: u64,
+    /// Size of the tree the proof was produced from
+    pub tree_size: u64,
+    /// Audit path from the leaf up to the root (base64url)
+    pub inclusion_path: Vec<String>,
+}
+
+impl InclusionProof {
+    /// Prove the entry at `leaf_index` in the tree over `entry_hashes`
+    pub fn new<S: AsRef<str>>(entry_hashes: &[S], leaf_index: u64) -> Result<Self, CoreError> {
+        Ok(Self {
+            leaf_index,
+            tree_size: entry_hashes.len() as u64,
+            inclusion_path: inclusion_proof(entry_hashes, leaf_index)?,
+        })
+    }
+
+    /// Whether `entry_hash` is included under `root` (see `verify_inclusion`)
+    pub fn verify(&self, root: &str, entry_hash: &str) -> Result<bool, CoreError> {
+        verify_inclusion(root, entry_hash, self.leaf_index, self.tree_size, &self.inclusion_path)
+    }
+}
+
+/// Serializable consistency proof between two tree sizes (RFC 9162 §4.12 `ConsistencyProofDataV2`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsistencyProof {
+    /// Size of the earlier tree
+    pub old_size: u64,
+    /// Size of the later tree
+    pub new_size: u64,
+    /// Nodes proving the earlier tree is a prefix of the later one (base64url)
+    pub consistency_path: Vec<String>,
+}
+
+impl ConsistencyProof {
+    /// Prove the first `old_size` entries are a prefix of `entry_hashes`
+    pub fn new<S: AsRef<str>>(entry_hashes: &[S], old_size: u64) -> Result<Self, CoreError> {
+        Ok(Self {
+            old_size,
+            new_size: entry_hashes.len() as u64,
+            consistency_path: consistency_proof(entry_hashes, old_size)?,
+        })
+    }
+
+    /// Whether the two roots are consistent (see `verify_consistency`)
+    pub fn verify(&self, old_root: &str, new_root: &str) -> Result<bool, CoreError> {
+        verify_consistency(self.old_size, self.new_size, old_root, new_root, &self.consistency_path)
+    }
+}
+
+/// Leaf hash: SHA-256(0x00 || data)
+pub(crate) fn hash_leaf(data: &[u8]) -> HashBytes {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Interior node hash: SHA-256(0x01 || left || right)
+pub(crate) fn hash_node(left: &HashBytes, right: &HashBytes) -> HashBytes {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn decode_leaves<S: AsRef<str>>(hashes: &[S]) -> Result<Vec<HashBytes>, CoreError> {
+    hashes.iter().map(|h| decode_hash(h.as_ref())).collect()
+}
+
+/// Largest power of two strictly less than `n` (n > 1)
+fn split_point(n: usize) -> usize {
+    1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
+
+/// MTH(D[n])
+fn tree_hash<L: AsRef<[u8]>>(leaves: &[L]) -> HashBytes {
+    match leaves.len() {
+        0 => Sha256::digest([]).into(),
+        1 => hash_leaf(leaves[0].as_ref()),
+        n => {
+            let k = split_point(n);
+            hash_node(&tree_hash(&leaves[..k]), &tree_hash(&leaves[k..]))
+        }
+    }
+}
+
+/// PATH(m, D[n])
+fn audit_path<L: AsRef<[u8]>>(m: usize, leaves: &[L]) -> Vec<HashBytes> {
+    let n = leaves.len();
+    if n <= 1 {
+        return Vec::new();
+    }
+    let k = split_point(n);
+    let (mut path, sibling) = if m < k {
+        (audit_path(m, &leaves[..k]), tree_hash(&leaves[k..]))
+    } else {
+        (audit_path(m - k, &leaves[k..]), tree_hash(&leaves[..k]))
+    };
+    path.push(sibling);
+    path
+}
+
+/// SUBPROOF(m, D[n], b)
+fn subproof<L: AsRef<[u8]>>(m: usize, leaves: &[L], complete: bool) -> Vec<HashBytes> {
+    let n = leaves.len();
+    if m == n {
+        return if complete { Vec::new() } else { alloc::vec![tree_hash(leaves)] };
+    }
+    let k = split_point(n);
+    let (mut proof, sibling) = if m <= k {
+        (subproof(m, &leaves[..k], complete), tree_hash(&leaves[k..]))
+    } else {
+        (subproof(m - k, &leaves[k..], false), tree_hash(&leaves[..k]))
+    };
+    proof.push(sibling);
+    proof
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_canonical;
+    use serde_json::json;
+
+    fn entry_hashes(n: u64) -> Vec<String> {
+        (0..n).map(|i| hash_canonical(&json!({ "id": i })).unwrap()).collect()
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_rfc6962_vectors() {
+        // Leaves and roots from the certificate-transparency reference tests
+        let leaves: Vec<Vec<u8>> = [
+            "",
+            "00",
+            "10",
+            "2021",
+            "3031",
+            "40414243",
+            "5051525354555657",
+            "606162636465666768696a6b6c6d6e6f",
+        ]
+        .iter()
+        .map(|l| hex(l))
+        .collect();
+        let roots = [
+            "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+            "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+            "aeb6bcfe274b70a14fb067a5e5578264db0fa9b51af5e0ba159158f329e06e77",
+            "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+            "4e3bbb1f7b478dcfe71fb631631519a3bca12c9aefca1612bfce4c13a86264d4",
+            "76e67dadbcdf1e10e1b74ddc608abd2f98dfb16fbce75277b5232a127f2087ef",
+            "ddb89be403809e325750d3d263cd78929c2942b7942a34b77e122c9594a74c8c",
+            "5dc9da79a70659a9ad559cb701ded9a2ab9d823aad2f4960cfe370eff4604328",
+        ];
+        for (i, root) in roots.iter().enumerate() {
+            assert_eq!(tree_hash(&leaves[..=i]).to_vec(), hex(root));
+        }
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let empty: [&str; 0] = [];
+        let root = merkle_root(&empty).unwrap();
+        assert_eq!(root, encode_hash(&Sha256::digest([])));
+    }
+
+    #[test]
+    fn test_inclusion_proofs_verify() {
+        for n in 1..=17 {
+            let hashes = entry_hashes(n);
+            let root = merkle_root(&hashes).unwrap();
+            for i in 0..n {
+                let proof = inclusion_proof(&hashes, i).unwrap();
+                assert!(verify_inclusion(&root, &hashes[i as usize], i, n, &proof).unwrap());
+                // Wrong index must not verify
+                if n > 1 {
+                    let other = (i + 1) % n;
+                    assert!(!verify_inclusion(&root, &hashes[i as usize], other, n, &proof).unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proof_out_of_range() {
+        let hashes = entry_hashes(3);
+        assert!(inclusion_proof(&hashes, 3).is_err());
+    }
+
+    #[test]
+    fn test_consistency_proofs_verify() {
+        let hashes = entry_hashes(17);
+        for n in 1..=17 {
+            let new_root = merkle_root(&hashes[..n]).unwrap();
+            for m in 1..=n {
+                let old_root = merkle_root(&hashes[..m]).unwrap();
+                let proof = consistency_proof(&hashes[..n], m as u64).unwrap();
+                assert!(verify_consistency(m as u64, n as u64, &old_root, &new_root, &proof).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_consistency_rejects_rewritten_history() {
+        let hashes = entry_hashes(8);
+        let mut rewritten = hashes.clone();
+        rewritten[2] = hash_canonical(&json!({ "id": "forged" })).unwrap();
+
+        let old_root = merkle_root(&hashes[..5]).unwrap();
+        let new_root = merkle_root(&rewritten).unwrap();
+        let proof = consistency_proof(&rewritten, 5).unwrap();
+        assert!(!verify_consistency(5, 8, &old_root, &new_root, &proof).unwrap());
+    }
+
+    #[test]
+    fn test_export_types_roundtrip() {
+        let hashes = entry_hashes(7);
+        let root = merkle_root(&hashes).unwrap();
+        let head = SignedTreeHead {
+            tree_size: 7,
+            timestamp: 1_700_000_000_000,
+            root_hash: root.clone(),
+            signature: "host-signature".into(),
+        };
+        let json = serde_json::to_value(&head).unwrap();
+        assert_eq!(json["tree_size"], 7);
+        assert_eq!(serde_json::from_value::<SignedTreeHead>(json).unwrap(), head);
+        assert_eq!(head.signing_input().unwrap(), tree_head_signing_input(1_700_000_000_000, 7, &root).unwrap());
+
+        let inclusion = InclusionProof::new(&hashes, 3).unwrap();
+        let parsed: InclusionProof = serde_json::from_value(serde_json::to_value(&inclusion).unwrap()).unwrap();
+        assert_eq!(parsed, inclusion);
+        assert!(parsed.verify(&root, &hashes[3]).unwrap());
+        assert!(!parsed.verify(&root, &hashes[4]).unwrap());
+
+        let old_root = merkle_root(&hashes[..4]).unwrap();
+        let consistency = ConsistencyProof::new(&hashes, 4).unwrap();
+        let parsed: ConsistencyProof = serde_json::from_value(serde_json::to_value(&consistency).unwrap()).unwrap();
+        assert_eq!(parsed, consistency);
+        assert!(parsed.verify(&old_root, &root).unwrap());
+        assert!(!parsed.verify(&root, &old_root).unwrap());
+    }
+
+    #[test]
+    fn test_tree_head_signing_input() {
+        let root = merkle_root(&entry_hashes(4)).unwrap();
+        let input = tree_head_signing_input(1_700_000_000_000, 4, &root).unwrap();
+        assert_eq!(input.len(), 50);
+        assert_eq!(&input[..2], &[0, 1]);
+        assert_eq!(&input[10..18], &4u64.to_be_bytes());
+        assert_eq!(&input[18..], &decode_hash(&root).unwrap());
+    }
+}
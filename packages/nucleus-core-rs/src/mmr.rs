@@ -0,0 +1,404 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::merkle::{hash_leaf, hash_node};
+use crate::{decode_hash, encode_hash, CoreError, HashBytes};
+
+/// Merkle Mountain Range over entry hashes
+///
+/// Append-only accumulator: each append adds one leaf plus the parents it
+/// completes, so the root and inclusion proofs never require rebuilding a
+/// tree over the whole chain.
+///
+/// Nodes are stored in post-order (the standard MMR position layout), which
+/// is also the persisted form: see `nodes()` / `from_nodes()`. Leaf and
+/// interior hashes use the RFC 6962 prefixes shared with `merkle`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleMountainRange {
+    nodes: Vec<HashBytes>,
+    leaf_count: u64,
+}
+
+/// Inclusion proof for a single leaf of an MMR
+///
+/// Serializable so it can be stored with an anchor or handed to a verifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MmrProof {
+    /// Zero-based leaf (entry) index
+    pub leaf_index: u64,
+    /// Total node count of the MMR the proof was produced from
+    pub mmr_size: u64,
+    /// Sibling hashes from the leaf up to its peak (base64url)
+    pub siblings: Vec<String>,
+    /// All peak hashes, left to right (base64url)
+    pub peaks: Vec<String>,
+}
+
+impl MerkleMountainRange {
+    /// Create an empty MMR
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restore an MMR from its persisted nodes (post-order)
+    ///
+    /// Every parent is recomputed from its children, so a corrupted or
+    /// tampered node list fails to load instead of yielding a wrong root.
+    /// Leaves are taken as given; compare `root()` against an anchored root
+    /// to check them.
+    pub fn from_nodes(nodes: Vec<HashBytes>) -> Result<Self, CoreError> {
+        let size = nodes.len() as u64;
+        if peak_positions(size).is_none() {
+            return Err(CoreError::InvalidMmrSize(size));
+        }
+        for pos in 0..size {
+            let height = pos_height(pos);
+            if height == 0 {
+                continue;
+            }
+            let left = &nodes[(pos - (1 << height)) as usize];
+            let right = &nodes[(pos - 1) as usize];
+            if hash_node(left, right) != nodes[pos as usize] {
+                return Err(CoreError::MmrNodeMismatch(pos));
+            }
+        }
+        let leaf_count = leaf_count_for_size(size);
+        Ok(Self { nodes, leaf_count })
+    }
+
+    /// Persistable node list (post-order)
+    pub fn nodes(&self) -> &[HashBytes] {
+        &self.nodes
+    }
+
+    /// Number of leaves (entries) appended
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    /// Total number of nodes (leaves + parents)
+    pub fn size(&self) -> u64 {
+        self.nodes.len() as u64
+    }
+
+    /// Append an entry hash (base64url) and return its leaf index
+    pub fn append(&mut self, entry_hash: &str) -> Result<u64, CoreError> {
+        let leaf = decode_hash(entry_hash)?;
+        let mut pos = self.size();
+        self.nodes.push(hash_leaf(&leaf));
+
+        // Merge completed subtrees: while the next position is a parent,
+        // its children are the last node and the node one subtree back
+        let mut height = 0;
+        while pos_height(pos + 1) > height {
+            pos += 1;
+            let left = &self.nodes[(pos - (2 << height)) as usize];
+            let right = &self.nodes[(pos - 1) as usize];
+            let parent = hash_node(left, right);
+            self.nodes.push(parent);
+            height += 1;
+        }
+
+        let index = self.leaf_count;
+        self.leaf_count += 1;
+        Ok(index)
+    }
+
+    /// Root over all peaks (base64url), or `None` when empty
+    pub fn root(&self) -> Option<String> {
+        let peaks: Vec<HashBytes> = peak_positions(self.size())?
+            .into_iter()
+            .map(|pos| self.nodes[pos as usize])
+            .collect();
+        bag_peaks(&peaks).map(|root| encode_hash(&root))
+    }
+
+    /// Produce an inclusion proof for the leaf at `leaf_index`
+    pub fn prove(&self, leaf_index: u64) -> Result<MmrProof, CoreError> {
+        if leaf_index >= self.leaf_count {
+            return Err(CoreError::OutOfRange {
+                what: "leaf index",
+                index: leaf_index,
+                size: self.leaf_count,
+            });
+        }
+
+        let size = self.size();
+        let peaks = peak_positions(size).unwrap_or_default();
+        let mut pos = leaf_position(leaf_index);
+        let mut height = 0;
+        let mut siblings = Vec::new();
+
+        while !peaks.contains(&pos) {
+            let (sibling, parent) = if pos_height(pos + 1) > height {
+                (pos - sibling_offset(height), pos + 1)
+            } else {
+                (pos + sibling_offset(height), pos + (2 << height))
+            };
+            siblings.push(encode_hash(&self.nodes[sibling as usize]));
+            pos = parent;
+            height += 1;
+        }
+
+        Ok(MmrProof {
+            leaf_index,
+            mmr_size: size,
+            siblings,
+            peaks: peaks
+                .into_iter()
+                .map(|p| encode_hash(&self.nodes[p as usize]))
+                .collect(),
+        })
+    }
+}
+
+/// Verify that `entry_hash` is included under `root` according to `proof`
+pub fn verify_mmr_proof(root: &str, entry_hash: &str, proof: &MmrProof) -> Result<bool, CoreError> {
+    let expected_root = decode_hash(root)?;
+    let peak_positions = match peak_positions(proof.mmr_size) {
+        Some(peaks) => peaks,
+        None => return Ok(false),
+    };
+    // No peak is higher than 63, so a longer path can never verify
+    if proof.peaks.len() != peak_positions.len()
+        || proof.leaf_index >= leaf_count_for_size(proof.mmr_size)
+        || proof.siblings.len() >= 64
+    {
+        return Ok(false);
+    }
+
+    // Walk from the leaf up to its peak. The proof is untrusted, so every
+    // step is checked; `pos < mmr_size` holds throughout, so `pos + 1` fits.
+    let mut pos = leaf_position(proof.leaf_index);
+    let mut acc = hash_leaf(&decode_hash(entry_hash)?);
+    for (height, sibling) in (0u32..).zip(&proof.siblings) {
+        let sibling = decode_hash(sibling)?;
+        let next = if pos_height(pos + 1) > height {
+            acc = hash_node(&sibling, &acc);
+            pos.checked_add(1)
+        } else {
+            acc = hash_node(&acc, &sibling);
+            1u64.checked_shl(height + 1).and_then(|step| pos.checked_add(step))
+        };
+        match next {
+            Some(parent) if parent < proof.mmr_size => pos = parent,
+            _ => return Ok(false),
+        }
+    }
+
+    let peaks = proof
+        .peaks
+        .iter()
+        .map(|p| decode_hash(p))
+        .collect::<Result<Vec<_>, _>>()?;
+    let peak_matches = peak_positions
+        .iter()
+        .position(|p| *p == pos)
+        .map(|i| peaks[i] == acc)
+        .unwrap_or(false);
+
+    Ok(peak_matches && bag_peaks(&peaks) == Some(expected_root))
+}
+
+/// Fold peaks right to left into a single root
+fn bag_peaks(peaks: &[HashBytes]) -> Option<HashBytes> {
+    let (last, rest) = peaks.split_last()?;
+    Some(rest.iter().rev().fold(*last, |acc, peak| hash_node(peak, &acc)))
+}
+
+/// Height of the node at a zero-based post-order position
+fn pos_height(pos: u64) -> u32 {
+    // Positions of the left-most nodes at each height are 2^(h+1) - 2, i.e.
+    // all-ones in one-based form; jump left until we reach one of them.
+    // One-based form of u64::MAX needs 65 bits, hence u128.
+    let mut pos = u128::from(pos) + 1;
+    while pos.count_ones() != 128 - pos.leading_zeros() {
+        pos -= (1 << (127 - pos.leading_zeros())) - 1;
+    }
+    127 - pos.leading_zeros()
+}
+
+/// Distance from a node to its sibling at `height`
+fn sibling_offset(height: u32) -> u64 {
+    (2 << height) - 1
+}
+
+/// Post-order position of the leaf with index `leaf_index`
+fn leaf_position(leaf_index: u64) -> u64 {
+    2 * leaf_index - u64::from(leaf_index.count_ones())
+}
+
+/// Peak positions for an MMR of `size` nodes, or `None` if no MMR has that size
+fn peak_positions(size: u64) -> Option<Vec<u64>> {
+    let mut peaks = Vec::new();
+    if size == 0 {
+        return Some(peaks);
+    }
+
+    let mut peak_size = u64::MAX >> size.leading_zeros();
+    let mut offset = 0;
+    let mut remaining = size;
+    while peak_size != 0 {
+        if remaining >= peak_size {
+            peaks.push(offset + peak_size - 1);
+            offset += peak_size;
+            remaining -= peak_size;
+        }
+        peak_size >>= 1;
+    }
+
+    if remaining == 0 {
+        Some(peaks)
+    } else {
+        None
+    }
+}
+
+/// Number of leaves in an MMR of `size` nodes (size must be valid)
+fn leaf_count_for_size(size: u64) -> u64 {
+    // A perfect subtree of 2^k - 1 nodes holds 2^(k-1) leaves
+    peak_positions(size)
+        .unwrap_or_default()
+        .iter()
+        .scan(0, |start, &peak| {
+            let nodes = peak + 1 - *start;
+            *start = peak + 1;
+            Some(nodes.div_ceil(2))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_canonical;
+    use serde_json::json;
+
+    fn entry_hash(i: u64) -> String {
+        hash_canonical(&json!({ "id": i })).unwrap()
+    }
+
+    fn build(leaves: u64) -> MerkleMountainRange {
+        let mut mmr = MerkleMountainRange::new();
+        for i in 0..leaves {
+            assert_eq!(mmr.append(&entry_hash(i)).unwrap(), i);
+        }
+        mmr
+    }
+
+    #[test]
+    fn test_empty() {
+        let mmr = MerkleMountainRange::new();
+        assert_eq!(mmr.root(), None);
+        assert!(mmr.prove(0).is_err());
+    }
+
+    #[test]
+    fn test_sizes() {
+        // leaves -> nodes: 1->1, 2->3, 3->4, 4->7, 5->8
+        let sizes = [1, 3, 4, 7, 8, 10, 11, 15];
+        for (i, expected) in sizes.iter().enumerate() {
+            let mmr = build(i as u64 + 1);
+            assert_eq!(mmr.size(), *expected);
+            assert_eq!(leaf_count_for_size(mmr.size()), i as u64 + 1);
+        }
+    }
+
+    #[test]
+    fn test_single_leaf_root() {
+        let mmr = build(1);
+        let leaf = decode_hash(&entry_hash(0)).unwrap();
+        assert_eq!(mmr.root(), Some(encode_hash(&hash_leaf(&leaf))));
+    }
+
+    #[test]
+    fn test_proofs_verify() {
+        for leaves in 1..=20 {
+            let mmr = build(leaves);
+            let root = mmr.root().unwrap();
+            for i in 0..leaves {
+                let proof = mmr.prove(i).unwrap();
+                assert!(verify_mmr_proof(&root, &entry_hash(i), &proof).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_proof_rejects_wrong_leaf() {
+        let mmr = build(9);
+        let root = mmr.root().unwrap();
+        let proof = mmr.prove(4).unwrap();
+        assert!(!verify_mmr_proof(&root, &entry_hash(5), &proof).unwrap());
+
+        let mut tampered = proof.clone();
+        tampered.siblings[0] = entry_hash(99);
+        assert!(!verify_mmr_proof(&root, &entry_hash(4), &tampered).unwrap());
+    }
+
+    #[test]
+    fn test_proof_serde_roundtrip() {
+        let mmr = build(7);
+        let proof = mmr.prove(5).unwrap();
+
+        let json = serde_json::to_value(&proof).unwrap();
+        assert_eq!(json["leaf_index"], 5);
+        assert_eq!(json["mmr_size"], mmr.size());
+
+        let parsed: MmrProof = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, proof);
+        assert!(verify_mmr_proof(&mmr.root().unwrap(), &entry_hash(5), &parsed).unwrap());
+    }
+
+    #[test]
+    fn test_proof_rejects_adversarial_sizes() {
+        let root = build(3).root().unwrap();
+        let forged = |siblings: usize| MmrProof {
+            leaf_index: 0,
+            mmr_size: u64::MAX,
+            siblings: alloc::vec![entry_hash(1); siblings],
+            peaks: alloc::vec![entry_hash(2)],
+        };
+
+        // 63 siblings reach the single peak of a u64::MAX-node MMR; more must
+        // be rejected without overflowing the position walk
+        for siblings in [63, 64, 70] {
+            assert!(!verify_mmr_proof(&root, &entry_hash(0), &forged(siblings)).unwrap());
+        }
+        assert_eq!(pos_height(u64::MAX - 1), 63);
+        assert_eq!(pos_height(u64::MAX), 0);
+    }
+
+    #[test]
+    fn test_root_changes_on_append() {
+        let mut mmr = build(5);
+        let before = mmr.root();
+        mmr.append(&entry_hash(5)).unwrap();
+        assert_ne!(before, mmr.root());
+    }
+
+    #[test]
+    fn test_from_nodes_roundtrip() {
+        let mmr = build(6);
+        let restored = MerkleMountainRange::from_nodes(mmr.nodes().to_vec()).unwrap();
+        assert_eq!(restored, mmr);
+
+        // 2 nodes is never a valid MMR size
+        assert!(MerkleMountainRange::from_nodes(mmr.nodes()[..2].to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_from_nodes_rejects_tampered_parents() {
+        let mmr = build(6);
+        // Position 6 is the parent of the first four leaves
+        let mut nodes = mmr.nodes().to_vec();
+        nodes[6] = decode_hash(&entry_hash(99)).unwrap();
+        let err = MerkleMountainRange::from_nodes(nodes).unwrap_err();
+        assert_eq!(err, CoreError::MmrNodeMismatch(6));
+
+        // A changed leaf no longer matches the parent above it
+        let mut nodes = mmr.nodes().to_vec();
+        nodes[0] = decode_hash(&entry_hash(99)).unwrap();
+        assert_eq!(MerkleMountainRange::from_nodes(nodes).unwrap_err(), CoreError::MmrNodeMismatch(2));
+    }
+}
@@ -0,0 +1,180 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+use crate::path::for_each_parent;
+
+/// Payload normalization applied before hashing
+///
+/// Lets semantically identical submissions from different clients hash
+/// identically. The policy is plain data so it can be stored in config
+/// alongside the ledger; applying the same policy always yields the same
+/// output.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NormalizationPolicy {
+    /// Trim leading/trailing whitespace from every string value (not keys)
+    pub trim_strings: bool,
+    /// Field paths whose string values are lowercased, e.g.
+    /// `"payload.subject_oid"`
+    pub lowercase_fields: Vec<String>,
+    /// Convert every string value (not keys) to Unicode NFC, so NFD input,
+    /// e.g. from macOS clients, hashes like its precomposed form
+    pub nfc: bool,
+}
+
+impl NormalizationPolicy {
+    /// Whether applying the policy can change anything
+    pub fn is_noop(&self) -> bool {
+        !self.trim_strings && self.lowercase_fields.is_empty() && !self.nfc
+    }
+
+    /// Normalize `value` in place
+    ///
+    /// NFC runs last so every string in the output is in NFC.
+    pub fn apply(&self, value: &mut Value) {
+        if self.trim_strings {
+            trim_strings(value);
+        }
+        for path in &self.lowercase_fields {
+            for_each_parent(value, path, &mut |obj, key| {
+                if let Some(field) = obj.get_mut(key) {
+                    lowercase_strings(field);
+                }
+            });
+        }
+        if self.nfc {
+            nfc_strings(value);
+        }
+    }
+
+    /// Return a normalized copy of `value`
+    pub fn normalize(&self, value: &Value) -> Value {
+        let mut normalized = value.clone();
+        self.apply(&mut normalized);
+        normalized
+    }
+}
+
+fn trim_strings(value: &mut Value) {
+    match value {
+        Value::String(s) => {
+            let trimmed = s.trim();
+            if trimmed.len() != s.len() {
+                *s = String::from(trimmed);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(trim_strings),
+        Value::Object(obj) => obj.values_mut().for_each(trim_strings),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+fn nfc_strings(value: &mut Value) {
+    match value {
+        Value::String(s) => {
+            if !is_nfc(s) {
+                *s = s.nfc().collect();
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(nfc_strings),
+        Value::Object(obj) => obj.values_mut().for_each(nfc_strings),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+fn lowercase_strings(value: &mut Value) {
+    match value {
+        Value::String(s) => *s = s.to_lowercase(),
+        Value::Array(items) => items.iter_mut().for_each(lowercase_strings),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::Object(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_canonical;
+    use alloc::vec;
+    use serde_json::json;
+
+    fn policy() -> NormalizationPolicy {
+        NormalizationPolicy {
+            trim_strings: true,
+            lowercase_fields: vec!["payload.subject_oid".into(), "payload.holders.oid".into()],
+            nfc: false,
+        }
+    }
+
+    #[test]
+    fn test_default_is_noop() {
+        let value = json!({"name": "  Alice "});
+        assert!(NormalizationPolicy::default().is_noop());
+        assert_eq!(NormalizationPolicy::default().normalize(&value), value);
+    }
+
+    #[test]
+    fn test_trim_strings() {
+        let policy = NormalizationPolicy { trim_strings: true, ..Default::default() };
+        let value = json!({" key ": " value ", "list": ["\ta\n", 1, null]});
+        assert_eq!(
+            policy.normalize(&value),
+            json!({" key ": "value", "list": ["a", 1, null]})
+        );
+    }
+
+    #[test]
+    fn test_lowercase_fields() {
+        let value = json!({
+            "payload": {
+                "subject_oid": "OID:Onoal:User:Alice",
+                "holders": [{"oid": "OID:A"}, {"oid": "OID:B"}],
+                "title": "Keep Case"
+            }
+        });
+        let normalized = policy().normalize(&value);
+        assert_eq!(normalized["payload"]["subject_oid"], "oid:onoal:user:alice");
+        assert_eq!(normalized["payload"]["holders"][1]["oid"], "oid:b");
+        assert_eq!(normalized["payload"]["title"], "Keep Case");
+    }
+
+    #[test]
+    fn test_nfc_hashes_nfd_like_nfc() {
+        // "Zoë" precomposed (U+00EB) and decomposed (e + U+0308)
+        let nfc = json!({"name": "Zo\u{00eb}", "tags": ["caf\u{00e9}"]});
+        let nfd = json!({"name": "Zoe\u{0308}", "tags": ["cafe\u{0301}"]});
+        assert_ne!(hash_canonical(&nfc).unwrap(), hash_canonical(&nfd).unwrap());
+
+        let policy = NormalizationPolicy { nfc: true, ..Default::default() };
+        assert!(!policy.is_noop());
+        assert_eq!(policy.normalize(&nfd), nfc);
+        assert_eq!(
+            hash_canonical(&policy.normalize(&nfc)).unwrap(),
+            hash_canonical(&policy.normalize(&nfd)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_equivalent_submissions_hash_equal() {
+        let a = json!({"payload": {"subject_oid": "OID:Onoal:User:Alice ", "note": " hi"}});
+        let b = json!({"payload": {"subject_oid": "oid:onoal:user:alice", "note": "hi"}});
+        assert_ne!(hash_canonical(&a).unwrap(), hash_canonical(&b).unwrap());
+        assert_eq!(
+            hash_canonical(&policy().normalize(&a)).unwrap(),
+            hash_canonical(&policy().normalize(&b)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_policy_roundtrips_through_config() {
+        let json = serde_json::to_value(policy()).unwrap();
+        let parsed: NormalizationPolicy = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, policy());
+
+        // Missing fields fall back to defaults
+        let parsed: NormalizationPolicy = serde_json::from_value(json!({"trim_strings": true})).unwrap();
+        assert!(parsed.lowercase_fields.is_empty());
+    }
+}
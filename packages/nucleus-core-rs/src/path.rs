@@ -0,0 +1,59 @@
+use alloc::string::String;
+use serde_json::{Map, Value};
+
+/// Call `f` with each object holding the last segment of a dotted field path
+///
+/// Paths such as `"meta.received_at"` address nested object fields. Arrays
+/// met along the way (including at the root) apply the rest of the path to
+/// each element. Shared by every path-based option so they read paths the
+/// same way.
+pub(crate) fn for_each_parent<F>(value: &mut Value, path: &str, f: &mut F)
+where
+    F: FnMut(&mut Map<String, Value>, &str),
+{
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                for_each_parent(item, path, f);
+            }
+        }
+        Value::Object(obj) => match path.split_once('.') {
+            None => f(obj, path),
+            Some((key, rest)) => {
+                if let Some(child) = obj.get_mut(key) {
+                    for_each_parent(child, rest, f);
+                }
+            }
+        },
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use serde_json::json;
+
+    fn visited(value: &mut Value, path: &str) -> Vec<String> {
+        let mut values = Vec::new();
+        for_each_parent(value, path, &mut |obj, key| {
+            if let Some(v) = obj.get(key) {
+                values.push(v.to_string());
+            }
+        });
+        values
+    }
+
+    #[test]
+    fn test_nested_and_array_paths() {
+        let mut value = json!([
+            {"meta": {"tags": [{"id": 1}, {"id": 2}, {"other": 3}]}},
+            {"meta": "not an object"},
+            {"meta": {"tags": {"id": 4}}}
+        ]);
+        assert_eq!(visited(&mut value, "meta.tags.id"), ["1", "2", "4"]);
+        assert!(visited(&mut value, "missing.id").is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::canonicalize::{write_canonical, write_canonical_object, write_error, ByteWriter};
+use crate::path::for_each_parent;
+use crate::{canonicalize_json, hash_bytes, CoreError};
+
+/// Stream-specific canonicalization rules layered on top of JCS
+///
+/// The default profile is plain `canonicalize_json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CanonicalProfile {
+    /// Top-level keys emitted first, in this order; remaining keys follow sorted
+    pub field_order: Vec<String>,
+    /// Field paths dropped before serialization, e.g. `"meta.received_at"`
+    pub excluded_fields: Vec<String>,
+}
+
+impl CanonicalProfile {
+    /// Canonical bytes of `value` under this profile
+    pub fn canonicalize(&self, value: &Value) -> Result<Vec<u8>, CoreError> {
+        if self.excluded_fields.is_empty() && self.field_order.is_empty() {
+            return canonicalize_json(value);
+        }
+
+        let mut value = value.clone();
+        for path in &self.excluded_fields {
+            for_each_parent(&mut value, path, &mut |obj, key| {
+                obj.remove(key);
+            });
+        }
+
+        let mut buffer = Vec::new();
+        let mut writer = ByteWriter(&mut buffer);
+        match &value {
+            Value::Object(obj) => write_canonical_object(&mut writer, obj, &self.field_order),
+            other => write_canonical(&mut writer, other),
+        }
+        .map_err(write_error)?;
+        Ok(buffer)
+    }
+
+    /// SHA-256 (base64url) of the canonical bytes under this profile
+    pub fn hash(&self, value: &Value) -> Result<String, CoreError> {
+        Ok(hash_bytes(&self.canonicalize(value)?))
+    }
+}
+
+/// Canonicalization profiles registered per stream
+///
+/// Streams without a registered profile use plain JCS.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileRegistry {
+    profiles: BTreeMap<String, CanonicalProfile>,
+}
+
+impl ProfileRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or replace) the profile for `stream`
+    pub fn register(&mut self, stream: impl Into<String>, profile: CanonicalProfile) {
+        self.profiles.insert(stream.into(), profile);
+    }
+
+    /// Profile registered for `stream`, if any
+    pub fn get(&self, stream: &str) -> Option<&CanonicalProfile> {
+        self.profiles.get(stream)
+    }
+
+    /// Canonical bytes of `value` for `stream`
+    pub fn canonicalize(&self, stream: &str, value: &Value) -> Result<Vec<u8>, CoreError> {
+        match self.get(stream) {
+            Some(profile) => profile.canonicalize(value),
+            None => canonicalize_json(value),
+        }
+    }
+
+    /// Hash of `value` for `stream`
+    pub fn hash(&self, stream: &str, value: &Value) -> Result<String, CoreError> {
+        Ok(hash_bytes(&self.canonicalize(stream, value)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash_canonical;
+    use alloc::vec;
+    use serde_json::json;
+
+    fn volatile_meta() -> CanonicalProfile {
+        CanonicalProfile {
+            excluded_fields: vec!["meta.received_at".into(), "meta.client".into()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_default_profile_is_jcs() {
+        let value = json!({"b": 1, "a": {"d": 2, "c": 3}});
+        assert_eq!(
+            CanonicalProfile::default().canonicalize(&value).unwrap(),
+            canonicalize_json(&value).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_excluded_fields() {
+        let a = json!({"id": "x", "meta": {"received_at": 1, "client": "web", "tag": "t"}});
+        let b = json!({"id": "x", "meta": {"received_at": 2, "tag": "t"}});
+
+        let profile = volatile_meta();
+        assert_eq!(
+            String::from_utf8(profile.canonicalize(&a).unwrap()).unwrap(),
+            r#"{"id":"x","meta":{"tag":"t"}}"#
+        );
+        assert_eq!(profile.hash(&a).unwrap(), profile.hash(&b).unwrap());
+        assert_ne!(hash_canonical(&a).unwrap(), hash_canonical(&b).unwrap());
+    }
+
+    #[test]
+    fn test_excluded_fields_in_arrays() {
+        let profile = CanonicalProfile {
+            excluded_fields: vec!["items.cache".into()],
+            ..Default::default()
+        };
+        let value = json!({"items": [{"id": 1, "cache": "a"}, {"id": 2}]});
+        assert_eq!(
+            String::from_utf8(profile.canonicalize(&value).unwrap()).unwrap(),
+            r#"{"items":[{"id":1},{"id":2}]}"#
+        );
+    }
+
+    #[test]
+    fn test_field_order() {
+        let profile = CanonicalProfile {
+            field_order: vec!["type".into(), "id".into(), "missing".into(), "type".into()],
+            ..Default::default()
+        };
+        let value = json!({"z": 1, "id": "x", "a": {"y": 1, "b": 2}, "type": "proof"});
+        assert_eq!(
+            String::from_utf8(profile.canonicalize(&value).unwrap()).unwrap(),
+            r#"{"type":"proof","id":"x","a":{"b":2,"y":1},"z":1}"#
+        );
+    }
+
+    #[test]
+    fn test_registry_per_stream() {
+        let mut registry = ProfileRegistry::new();
+        registry.register("proofs", volatile_meta());
+
+        let value = json!({"id": "x", "meta": {"received_at": 1}});
+        assert_eq!(
+            registry.hash("proofs", &value).unwrap(),
+            hash_canonical(&json!({"id": "x", "meta": {}})).unwrap()
+        );
+        // Unregistered streams use plain JCS
+        assert_eq!(registry.hash("assets", &value).unwrap(), hash_canonical(&value).unwrap());
+    }
+}
@@ -0,0 +1,172 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    canonicalize_json, compute_entry_hash, hash_canonical, inclusion_proof, merkle_root, CoreError, HashDomain,
+    HashVersion, MerkleMountainRange,
+};
+
+/// Golden vectors for the hashing primitives
+///
+/// Only the Rust tests check them today. The file is plain JSON so the WASM
+/// and TypeScript suites can load it directly. Vectors
+/// are append-only: an expectation only changes together with a new
+/// `HashVersion`.
+pub const GOLDEN_VECTORS_JSON: &str = include_str!("../vectors/golden.json");
+
+/// Expected outputs for a fixed set of inputs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GoldenVectors {
+    /// Canonicalization and payload hashing cases
+    pub canonical: Vec<CanonicalVector>,
+    /// Hash chains with their tree roots and proofs
+    pub chains: Vec<ChainVector>,
+}
+
+/// One JCS input with its canonical form and hash
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CanonicalVector {
+    /// Case name reported in mismatches
+    pub name: String,
+    /// JSON value passed to `canonicalize_json` and `hash_canonical`
+    pub input: Value,
+    /// `canonicalize_json` output as UTF-8
+    pub canonical: String,
+    /// `hash_canonical` output
+    pub hash: String,
+}
+
+/// A chain of records hashed under one `HashVersion`
+///
+/// Record `i` has sequence `i` and links to the hash of record `i - 1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainVector {
+    /// Case name reported in mismatches
+    pub name: String,
+    /// `HashVersion` number
+    pub hash_version: u32,
+    /// `HashDomain` used for every record (ignored before V3)
+    pub ledger_id: String,
+    /// Entry type used for every record (ignored before V3)
+    pub entry_type: String,
+    /// Record payloads in chain order
+    pub records: Vec<Value>,
+    /// `compute_entry_hash` output per record
+    pub entry_hashes: Vec<String>,
+    /// RFC 6962 root over `entry_hashes`
+    pub merkle_root: String,
+    /// RFC 6962 audit path for the last entry
+    pub inclusion_proof: Vec<String>,
+    /// MMR root after appending every entry hash
+    pub mmr_root: String,
+}
+
+impl GoldenVectors {
+    /// Parse the bundled vectors
+    pub fn load() -> Result<Self, CoreError> {
+        Self::from_json(GOLDEN_VECTORS_JSON)
+    }
+
+    /// Parse vectors from JSON, e.g. a newer copy of the file
+    pub fn from_json(json: &str) -> Result<Self, CoreError> {
+        serde_json::from_str(json).map_err(|e| CoreError::InvalidJson(format!("{}", e)))
+    }
+
+    /// Recompute every expectation with this build and describe each mismatch
+    pub fn mismatches(&self) -> Result<Vec<String>, CoreError> {
+        let mut mismatches = Vec::new();
+        for vector in &self.canonical {
+            vector.check(&mut mismatches)?;
+        }
+        for vector in &self.chains {
+            vector.check(&mut mismatches)?;
+        }
+        Ok(mismatches)
+    }
+}
+
+impl CanonicalVector {
+    fn check(&self, mismatches: &mut Vec<String>) -> Result<(), CoreError> {
+        let canonical = String::from_utf8_lossy(&canonicalize_json(&self.input)?).into_owned();
+        expect(mismatches, &self.name, "canonical", &self.canonical, &canonical);
+        expect(mismatches, &self.name, "hash", &self.hash, &hash_canonical(&self.input)?);
+        Ok(())
+    }
+}
+
+impl ChainVector {
+    fn check(&self, mismatches: &mut Vec<String>) -> Result<(), CoreError> {
+        let version = HashVersion::from_u32(self.hash_version)?;
+        let domain = HashDomain { ledger_id: &self.ledger_id, entry_type: &self.entry_type };
+
+        let mut hashes: Vec<String> = Vec::with_capacity(self.records.len());
+        for (sequence, record) in (0u64..).zip(&self.records) {
+            let prev_hash = hashes.last().map(String::as_str);
+            hashes.push(compute_entry_hash(version, &domain, record, prev_hash, sequence)?);
+        }
+        if hashes != self.entry_hashes {
+            mismatches.push(format!("{}: entry_hashes differ", self.name));
+        }
+        if hashes.is_empty() {
+            return Ok(());
+        }
+
+        expect(mismatches, &self.name, "merkle_root", &self.merkle_root, &merkle_root(&hashes)?);
+        if inclusion_proof(&hashes, hashes.len() as u64 - 1)? != self.inclusion_proof {
+            mismatches.push(format!("{}: inclusion_proof differs", self.name));
+        }
+
+        let mut mmr = MerkleMountainRange::new();
+        for hash in &hashes {
+            mmr.append(hash)?;
+        }
+        expect(mismatches, &self.name, "mmr_root", &self.mmr_root, &mmr.root().unwrap_or_default());
+        Ok(())
+    }
+}
+
+/// Panic with every mismatch if this build disagrees with the bundled vectors
+pub fn assert_golden_vectors() {
+    let vectors = GoldenVectors::load().expect("bundled golden vectors parse");
+    let mismatches = vectors.mismatches().expect("golden vector inputs are valid");
+    assert!(mismatches.is_empty(), "golden vector mismatches:\n{}", mismatches.join("\n"));
+}
+
+fn expect(mismatches: &mut Vec<String>, name: &str, what: &str, expected: &str, actual: &str) {
+    if expected != actual {
+        mismatches.push(format!("{}: {} expected {:?}, got {:?}", name, what, expected, actual));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bundled_vectors_hold() {
+        assert_golden_vectors();
+    }
+
+    #[test]
+    fn test_vectors_cover_every_hash_version() {
+        let vectors = GoldenVectors::load().unwrap();
+        for version in [HashVersion::V1, HashVersion::V2, HashVersion::V3] {
+            assert!(vectors.chains.iter().any(|c| c.hash_version == version.as_u32()));
+        }
+    }
+
+    #[test]
+    fn test_reports_mismatches() {
+        let mut vectors = GoldenVectors::load().unwrap();
+        vectors.canonical[0].input = json!({"changed": true});
+        vectors.chains[0].records[0] = json!({"changed": true});
+
+        let mismatches = vectors.mismatches().unwrap();
+        assert!(mismatches.iter().any(|m| m.contains("canonical expected")));
+        assert!(mismatches.iter().any(|m| m.contains("entry_hashes differ")));
+    }
+}
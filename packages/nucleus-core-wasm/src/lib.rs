@@ -0,0 +1,72 @@
+use nucleus_core_rs::{
+    canonicalize_json, hash_canonical, hash_canonical_batch, verify_external_data, CoreError, ExternalCommitment,
+};
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+/// Convert a core error into a JS `Error` carrying the stable `code` and `retryable` flag
+fn to_js_error(error: CoreError) -> JsValue {
+    let js_error = js_sys::Error::new(&error.to_string());
+    // Setting properties on a fresh Error object cannot fail
+    let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("code"), &JsValue::from_str(error.code()));
+    let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("retryable"), &JsValue::from_bool(error.is_retryable()));
+    js_error.into()
+}
+
+/// Compute SHA-256 hash of a canonical JSON representation
+/// Returns base64url-encoded hash string
+#[wasm_bindgen]
+pub fn compute_hash(record_without_hash: JsValue) -> Result<String, JsValue> {
+    // Deserialize from JS
+    let value: Value = serde_wasm_bindgen::from_value(record_without_hash)
+        .map_err(|e| to_js_error(CoreError::InvalidJson(format!("{}", e))))?;
+    
+    hash_canonical(&value).map_err(to_js_error)
+}
+
+/// Compute hashes for an array of records in one call
+/// Returns an array of base64url-encoded hash strings, in input order
+#[wasm_bindgen]
+pub fn compute_hashes(records_without_hash: JsValue) -> Result<JsValue, JsValue> {
+    let values: Vec<Value> = serde_wasm_bindgen::from_value(records_without_hash)
+        .map_err(|e| to_js_error(CoreError::InvalidJson(format!("{}", e))))?;
+    
+    let hashes = hash_canonical_batch(&values).map_err(to_js_error)?;
+    serde_wasm_bindgen::to_value(&hashes)
+        .map_err(|e| to_js_error(CoreError::Serialization(format!("{}", e))))
+}
+
+/// Canonicalize JSON (for testing/debugging)
+/// Returns canonical JSON bytes
+#[wasm_bindgen]
+pub fn canonicalize(record_without_hash: JsValue) -> Result<Vec<u8>, JsValue> {
+    let value: Value = serde_wasm_bindgen::from_value(record_without_hash)
+        .map_err(|e| to_js_error(CoreError::InvalidJson(format!("{}", e))))?;
+    
+    canonicalize_json(&value).map_err(to_js_error)
+}
+
+/// Build a hash-only commitment payload for off-ledger data
+/// Returns `{ data_hash, hash_alg, size, location?, media_type? }`
+#[wasm_bindgen]
+pub fn commit_external_data(
+    data: &[u8],
+    location: Option<String>,
+    media_type: Option<String>,
+) -> Result<JsValue, JsValue> {
+    let mut commitment = ExternalCommitment::new(data);
+    commitment.location = location;
+    commitment.media_type = media_type;
+    
+    serde_wasm_bindgen::to_value(&commitment)
+        .map_err(|e| to_js_error(CoreError::Serialization(format!("{}", e))))
+}
+
+/// Check off-ledger data against a commitment payload
+#[wasm_bindgen(js_name = verify_external_data)]
+pub fn verify_external_data_js(payload: JsValue, data: &[u8]) -> Result<bool, JsValue> {
+    let payload: Value = serde_wasm_bindgen::from_value(payload)
+        .map_err(|e| to_js_error(CoreError::InvalidJson(format!("{}", e))))?;
+    
+    verify_external_data(&payload, data).map_err(to_js_error)
+}
@@ -74,8 +74,10 @@ export async function createNucleus(config: CreateNucleusConfig): Promise<Nucleu
     try {
       return wasm.compute_hash(recordWithoutHash);
     } catch (error) {
+      // Keep the WASM error (with its stable `code`) reachable via `cause`
       throw new Error(
-        `WASM hash computation failed: ${error instanceof Error ? error.message : String(error)}`
+        `WASM hash computation failed: ${error instanceof Error ? error.message : String(error)}`,
+        { cause: error }
       );
     }
   };
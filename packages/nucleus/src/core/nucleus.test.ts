@@ -169,6 +169,24 @@ describe("Nucleus Engine", () => {
       ).rejects.toThrow("Validation failed for testing");
     });
 
+    it("should keep the hash error code reachable via cause", async () => {
+      const failingHash = (): string => {
+        throw Object.assign(new Error("Invalid JSON: bad input"), {
+          code: "INVALID_JSON",
+          retryable: false,
+        });
+      };
+      const failing = new Nucleus(storage, failingHash);
+
+      const error = await failing
+        .append({ module: "test", chainId: "test-chain", body: {} })
+        .catch((e: unknown) => e);
+
+      expect(error).toBeInstanceOf(Error);
+      expect((error as Error).message).toContain("Hash computation failed");
+      expect((error as Error).cause).toMatchObject({ code: "INVALID_JSON", retryable: false });
+    });
+
     it("should pass context to module validator", async () => {
       let capturedContext: unknown = null;
 
@@ -94,8 +94,10 @@ export class Nucleus {
     try {
       hash = this.computeHash(recordWithoutHash);
     } catch (error) {
+      // Keep the underlying error (and its stable `code`) reachable via `cause`
       throw new Error(
-        `Hash computation failed: ${error instanceof Error ? error.message : String(error)}`
+        `Hash computation failed: ${error instanceof Error ? error.message : String(error)}`,
+        { cause: error }
       );
     }
 